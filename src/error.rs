@@ -3,19 +3,37 @@ use std::{
     fmt::{Display, Error as FmtError, Formatter},
 };
 
+#[derive(Clone, Copy, Eq, Debug, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 #[derive(Eq, Debug, PartialEq)]
 pub enum Error {
-    UnexpectedEnd,
-    UnexpectedChar(char),
-    UnexpectedToken(String),
+    UnexpectedEnd(Position),
+    UnexpectedChar(char, Position),
+    UnexpectedToken(String, Position),
+    UnexpectedNonWhitespace(Position),
+    InvalidEscape(Position),
+    InvalidPath(String),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match self {
-            Self::UnexpectedEnd => write!(f, "unexpected end"),
-            Self::UnexpectedChar(c) => write!(f, "unexpected token '{c}'"),
-            Self::UnexpectedToken(t) => write!(f, "unexpected token '{t}'"),
+            Self::UnexpectedEnd(p) => write!(f, "unexpected end at {p}"),
+            Self::UnexpectedChar(c, p) => write!(f, "unexpected token '{c}' at {p}"),
+            Self::UnexpectedToken(t, p) => write!(f, "unexpected token '{t}' at {p}"),
+            Self::UnexpectedNonWhitespace(p) => write!(f, "unexpected non-whitespace at {p}"),
+            Self::InvalidEscape(p) => write!(f, "invalid escape sequence at {p}"),
+            Self::InvalidPath(path) => write!(f, "invalid path '{path}'"),
         }
     }
 }
@@ -24,20 +42,40 @@ impl StdError for Error {}
 
 #[cfg(test)]
 mod tests {
-    use crate::tokenize::Token;
+    use crate::tokenize::TokenKind;
 
     use super::*;
 
     #[test]
     fn display_test() {
-        assert_eq!(&Error::UnexpectedEnd.to_string(), "unexpected end");
+        let p = Position {
+            line: 4,
+            column: 12,
+        };
+
+        assert_eq!(
+            &Error::UnexpectedEnd(p).to_string(),
+            "unexpected end at line 4, column 12"
+        );
+        assert_eq!(
+            &Error::UnexpectedChar('a', p).to_string(),
+            "unexpected token 'a' at line 4, column 12"
+        );
+        assert_eq!(
+            &Error::UnexpectedToken(TokenKind::ValueSeparator.to_string(), p).to_string(),
+            "unexpected token ',' at line 4, column 12"
+        );
+        assert_eq!(
+            &Error::UnexpectedNonWhitespace(p).to_string(),
+            "unexpected non-whitespace at line 4, column 12"
+        );
         assert_eq!(
-            &Error::UnexpectedChar('a').to_string(),
-            "unexpected token 'a'"
+            &Error::InvalidEscape(p).to_string(),
+            "invalid escape sequence at line 4, column 12"
         );
         assert_eq!(
-            &Error::UnexpectedToken(Token::BeginArray.to_string()).to_string(),
-            "unexpected token '['"
+            &Error::InvalidPath("$.[".to_string()).to_string(),
+            "invalid path '$.['"
         );
     }
 }
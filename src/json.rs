@@ -1,6 +1,10 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
 
-use crate::{error::Error, parse::parse, tokenize::tokenize};
+use crate::{error::Error, number::Number, parse::parse, tokenize::tokenize};
 
 #[derive(Debug, PartialEq)]
 pub enum Value {
@@ -8,7 +12,7 @@ pub enum Value {
     Bool(bool),
     Object(HashMap<String, Value>),
     Array(Vec<Value>),
-    Number(f64),
+    Number(Number),
     String(String),
 }
 
@@ -21,3 +25,183 @@ impl FromStr for Value {
         Ok(value)
     }
 }
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Number(n) => write!(f, "{n}"),
+            Self::String(s) => write!(f, "{}", escape_string(s)),
+            Self::Array(a) => {
+                write!(f, "[")?;
+                for (i, v) in a.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                write!(f, "]")
+            }
+            Self::Object(o) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in sorted_entries(o).enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{v}", escape_string(k))?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl Value {
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        let mut s = String::new();
+        self.write_pretty(&mut s, indent, 0);
+        s
+    }
+
+    fn write_pretty(&self, out: &mut String, indent: usize, depth: usize) {
+        match self {
+            Self::Null | Self::Bool(_) | Self::Number(_) | Self::String(_) => {
+                out.push_str(&self.to_string());
+            }
+            Self::Array(a) if a.is_empty() => out.push_str("[]"),
+            Self::Array(a) => {
+                out.push('[');
+                for (i, v) in a.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                    out.push_str(&" ".repeat(indent * (depth + 1)));
+                    v.write_pretty(out, indent, depth + 1);
+                }
+                out.push('\n');
+                out.push_str(&" ".repeat(indent * depth));
+                out.push(']');
+            }
+            Self::Object(o) if o.is_empty() => out.push_str("{}"),
+            Self::Object(o) => {
+                out.push('{');
+                for (i, (k, v)) in sorted_entries(o).enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                    out.push_str(&" ".repeat(indent * (depth + 1)));
+                    out.push_str(&escape_string(k));
+                    out.push_str(": ");
+                    v.write_pretty(out, indent, depth + 1);
+                }
+                out.push('\n');
+                out.push_str(&" ".repeat(indent * depth));
+                out.push('}');
+            }
+        }
+    }
+}
+
+// `HashMap` iteration order is randomized per-process; sort by key so the same `Value`
+// always serializes to the same text, which is what "canonical form" promises.
+fn sorted_entries(o: &HashMap<String, Value>) -> impl Iterator<Item = (&String, &Value)> {
+    let mut entries: Vec<_> = o.iter().collect();
+    entries.sort_by_key(|(k, _)| *k);
+    entries.into_iter()
+}
+
+fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{0008}' => out.push_str("\\b"),
+            '\u{000C}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c if c.is_ascii() => out.push(c),
+            c => {
+                let mut buf = [0u16; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    out.push_str(&format!("\\u{unit:04x}"));
+                }
+            }
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        assert_eq!(&Value::Null.to_string(), "null");
+        assert_eq!(&Value::Bool(true).to_string(), "true");
+        assert_eq!(&Value::Number(Number::Int(123)).to_string(), "123");
+        assert_eq!(
+            &Value::String("he said \"hi\"\n".to_string()).to_string(),
+            "\"he said \\\"hi\\\"\\n\""
+        );
+        assert_eq!(
+            &Value::String("café".to_string()).to_string(),
+            "\"caf\\u00e9\""
+        );
+        assert_eq!(
+            &Value::Array(vec![Value::Number(Number::Int(1)), Value::Bool(false)]).to_string(),
+            "[1,false]"
+        );
+        assert_eq!(
+            &Value::Object([("a".to_string(), Value::Null)].into()).to_string(),
+            "{\"a\":null}"
+        );
+    }
+
+    #[test]
+    fn object_display_is_deterministically_ordered() {
+        let value = Value::Object(
+            [
+                ("b".to_string(), Value::Number(Number::Int(2))),
+                ("a".to_string(), Value::Number(Number::Int(1))),
+                ("c".to_string(), Value::Number(Number::Int(3))),
+            ]
+            .into(),
+        );
+
+        assert_eq!(&value.to_string(), "{\"a\":1,\"b\":2,\"c\":3}");
+    }
+
+    #[test]
+    fn to_string_pretty_test() {
+        assert_eq!(&Value::Array(vec![]).to_string_pretty(2), "[]");
+        assert_eq!(&Value::Object([].into()).to_string_pretty(2), "{}");
+        assert_eq!(
+            &Value::Array(vec![
+                Value::Number(Number::Int(1)),
+                Value::Number(Number::Int(2))
+            ])
+            .to_string_pretty(2),
+            "[\n  1,\n  2\n]"
+        );
+        assert_eq!(
+            &Value::Object([("a".to_string(), Value::Null)].into()).to_string_pretty(2),
+            "{\n  \"a\": null\n}"
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let json = r#"{"key":[1,2.5,"str",true,null]}"#;
+        let value: Value = json.parse().unwrap();
+        assert_eq!(&value.to_string(), json);
+    }
+}
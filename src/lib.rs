@@ -0,0 +1,6 @@
+pub mod error;
+pub mod json;
+pub mod number;
+pub mod parse;
+pub mod path;
+pub mod tokenize;
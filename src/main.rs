@@ -0,0 +1,89 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use json_parser_rs::{error::Error, parse::parse, tokenize::tokenize};
+
+enum Mode {
+    Tokens,
+    Ast,
+    Canonical,
+}
+
+fn main() -> ExitCode {
+    let mut mode = Mode::Canonical;
+    let mut path = None;
+
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "-t" => mode = Mode::Tokens,
+            "-a" => mode = Mode::Ast,
+            arg => path = Some(arg.to_string()),
+        }
+    }
+
+    let input = match read_input(path.as_deref()) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(e) = run(&input, mode) {
+        eprintln!("error: {e}");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn read_input(path: Option<&str>) -> io::Result<String> {
+    match path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+fn run(input: &str, mode: Mode) -> Result<(), Error> {
+    let tokens = tokenize(input)?;
+
+    match mode {
+        Mode::Tokens => {
+            for token in &tokens {
+                println!("{:?} at {}", token.kind, token.position);
+            }
+        }
+        Mode::Ast => {
+            let value = parse(&tokens)?;
+            println!("{value:#?}");
+        }
+        Mode::Canonical => {
+            let value = parse(&tokens)?;
+            println!("{}", value.to_string_pretty(2));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_canonical_mode_pretty_prints() {
+        assert!(run(r#"{"a":[1,2]}"#, Mode::Canonical).is_ok());
+    }
+
+    #[test]
+    fn run_canonical_mode_surfaces_errors() {
+        assert!(run("{", Mode::Canonical).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Number {
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Big(String),
+}
+
+impl Number {
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int(n) => Some(*n),
+            Self::UInt(n) => i64::try_from(*n).ok(),
+            Self::Float(n) => float_to_int(*n),
+            Self::Big(s) => s.parse().ok(),
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Int(n) => u64::try_from(*n).ok(),
+            Self::UInt(n) => Some(*n),
+            Self::Float(n) => float_to_int(*n).and_then(|n| u64::try_from(n).ok()),
+            Self::Big(s) => s.parse().ok(),
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Int(n) => Some(*n as f64),
+            Self::UInt(n) => Some(*n as f64),
+            Self::Float(n) => Some(*n),
+            Self::Big(s) => s.parse().ok(),
+        }
+    }
+}
+
+fn float_to_int(n: f64) -> Option<i64> {
+    if n.fract() == 0.0 && n >= i64::MIN as f64 && n <= i64::MAX as f64 {
+        Some(n as i64)
+    } else {
+        None
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Int(n) => write!(f, "{n}"),
+            Self::UInt(n) => write!(f, "{n}"),
+            Self::Float(n) => write!(f, "{}", format_float(*n)),
+            Self::Big(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Rust's default `{}` formatting for `f64` expands large/tiny finite values to their full
+/// decimal form (hundreds of digits for `1e300`). Switch to scientific notation outside a
+/// normal magnitude range so the output stays compact and still parses back as valid JSON.
+fn format_float(n: f64) -> String {
+    let abs = n.abs();
+    if abs != 0.0 && !(1e-6..1e16).contains(&abs) {
+        format!("{n:e}")
+    } else {
+        n.to_string()
+    }
+}
+
+/// Parses the digits of a JSON integer, widening from `i64` to `u64` and finally to an
+/// arbitrary-precision `Big` string so no precision is lost for out-of-range values.
+/// Returns `None` if `s` isn't an optionally-signed run of digits (e.g. a bare `-`), so the
+/// caller can reject it instead of silently keeping a non-numeric string as `Big`.
+pub(crate) fn parse_integer(s: &str) -> Option<Number> {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(if let Ok(n) = s.parse::<i64>() {
+        Number::Int(n)
+    } else if let Ok(n) = s.parse::<u64>() {
+        Number::UInt(n)
+    } else {
+        Number::Big(s.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accessors_test() {
+        assert_eq!(Number::Int(-1).as_i64(), Some(-1));
+        assert_eq!(Number::Int(-1).as_u64(), None);
+        assert_eq!(Number::UInt(u64::MAX).as_i64(), None);
+        assert_eq!(Number::UInt(u64::MAX).as_u64(), Some(u64::MAX));
+        assert_eq!(Number::Float(1.5).as_f64(), Some(1.5));
+        assert_eq!(Number::Float(1.5).as_i64(), None);
+        assert_eq!(Number::Float(2.0).as_i64(), Some(2));
+        assert_eq!(
+            Number::Big("18446744073709551616".to_string()).as_f64(),
+            Some(18446744073709551616f64)
+        );
+        assert_eq!(
+            Number::Big("18446744073709551616".to_string()).as_i64(),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_integer_test() {
+        assert_eq!(parse_integer("123"), Some(Number::Int(123)));
+        assert_eq!(parse_integer("-123"), Some(Number::Int(-123)));
+        assert_eq!(
+            parse_integer("18446744073709551615"),
+            Some(Number::UInt(u64::MAX))
+        );
+        assert_eq!(
+            parse_integer("99999999999999999999999"),
+            Some(Number::Big("99999999999999999999999".to_string()))
+        );
+        assert_eq!(parse_integer("-"), None);
+        assert_eq!(parse_integer(""), None);
+    }
+
+    #[test]
+    fn float_display_test() {
+        assert_eq!(&Number::Float(123.45).to_string(), "123.45");
+        assert_eq!(&Number::Float(0.0).to_string(), "0");
+        assert_eq!(&Number::Float(1e300).to_string(), "1e300");
+        assert_eq!(&Number::Float(1e-300).to_string(), "1e-300");
+        assert_eq!(&Number::Float(-1.5e300).to_string(), "-1.5e300");
+    }
+}
@@ -1,175 +1,273 @@
 use std::collections::HashMap;
 
-use crate::{error::Error, json::Value, tokenize::Token};
+use crate::{
+    error::{Error, Position},
+    json::Value,
+    tokenize::{Token, TokenKind},
+};
 
 pub fn parse(tokens: &[Token]) -> Result<Value, Error> {
     let mut p = tokens;
-    let value = parse_value(&mut p)?;
-    if !p.is_empty() {
-        return Err(Error::UnexpectedNonWhitespace);
+    let mut last = Position { line: 1, column: 1 };
+    let value = parse_value(&mut p, &mut last)?;
+    if let Some(t) = p.first() {
+        return Err(Error::UnexpectedNonWhitespace(t.position));
     }
     Ok(value)
 }
 
-fn parse_value(tokens: &mut &[Token]) -> Result<Value, Error> {
+// Snapshots the position of the consumed token before advancing past it, so an
+// `UnexpectedEnd` raised once the slice runs dry can still point at the last real token.
+fn advance(tokens: &mut &[Token], last: &mut Position) {
+    if let Some(t) = tokens.first() {
+        *last = t.position;
+    }
+    *tokens = &tokens[1..];
+}
+
+fn parse_value(tokens: &mut &[Token], last: &mut Position) -> Result<Value, Error> {
     match tokens.first() {
-        Some(Token::Null) => {
-            *tokens = &tokens[1..];
+        Some(Token {
+            kind: TokenKind::Null,
+            ..
+        }) => {
+            advance(tokens, last);
             Ok(Value::Null)
         }
-        Some(Token::False) => {
-            *tokens = &tokens[1..];
+        Some(Token {
+            kind: TokenKind::False,
+            ..
+        }) => {
+            advance(tokens, last);
             Ok(Value::Bool(false))
         }
-        Some(Token::True) => {
-            *tokens = &tokens[1..];
+        Some(Token {
+            kind: TokenKind::True,
+            ..
+        }) => {
+            advance(tokens, last);
             Ok(Value::Bool(true))
         }
-        Some(Token::Number(n)) => {
-            *tokens = &tokens[1..];
-            Ok(Value::Number(*n))
+        Some(Token {
+            kind: TokenKind::Number(n),
+            ..
+        }) => {
+            let n = n.clone();
+            advance(tokens, last);
+            Ok(Value::Number(n))
         }
-        Some(Token::String(s)) => {
-            *tokens = &tokens[1..];
-            Ok(Value::String(s.to_string()))
+        Some(Token {
+            kind: TokenKind::String(s),
+            ..
+        }) => {
+            let s = s.to_string();
+            advance(tokens, last);
+            Ok(Value::String(s))
         }
-        Some(Token::BeginObject) => parse_object(tokens),
-        Some(Token::BeginArray) => parse_array(tokens),
-        Some(t) => Err(Error::UnexpectedToken(t.to_string())),
-        None => Err(Error::UnexpectedEnd),
+        Some(Token {
+            kind: TokenKind::BeginObject,
+            ..
+        }) => parse_object(tokens, last),
+        Some(Token {
+            kind: TokenKind::BeginArray,
+            ..
+        }) => parse_array(tokens, last),
+        Some(t) => Err(Error::UnexpectedToken(t.kind.to_string(), t.position)),
+        None => Err(Error::UnexpectedEnd(*last)),
     }
 }
 
-fn parse_object(tokens: &mut &[Token]) -> Result<Value, Error> {
+fn parse_object(tokens: &mut &[Token], last: &mut Position) -> Result<Value, Error> {
     match tokens.first() {
-        Some(Token::BeginObject) => *tokens = &tokens[1..],
-        Some(t) => return Err(Error::UnexpectedToken(t.to_string())),
-        None => return Err(Error::UnexpectedEnd),
+        Some(Token {
+            kind: TokenKind::BeginObject,
+            ..
+        }) => advance(tokens, last),
+        Some(t) => return Err(Error::UnexpectedToken(t.kind.to_string(), t.position)),
+        None => return Err(Error::UnexpectedEnd(*last)),
     }
 
     let mut o = HashMap::new();
 
     loop {
-        if let Some(Token::EndObject) = tokens.first() {
-            *tokens = &tokens[1..];
+        if let Some(Token {
+            kind: TokenKind::EndObject,
+            ..
+        }) = tokens.first()
+        {
+            advance(tokens, last);
             return Ok(Value::Object(o));
         }
 
         if !o.is_empty() {
             match tokens.first() {
-                Some(Token::ValueSeparator) => *tokens = &tokens[1..],
-                Some(t) => return Err(Error::UnexpectedToken(t.to_string())),
-                None => return Err(Error::UnexpectedEnd),
+                Some(Token {
+                    kind: TokenKind::ValueSeparator,
+                    ..
+                }) => advance(tokens, last),
+                Some(t) => return Err(Error::UnexpectedToken(t.kind.to_string(), t.position)),
+                None => return Err(Error::UnexpectedEnd(*last)),
             }
         }
 
         let k = match tokens.first() {
-            Some(Token::String(k)) => {
-                *tokens = &tokens[1..];
-                k.to_string()
+            Some(Token {
+                kind: TokenKind::String(k),
+                ..
+            }) => {
+                let k = k.to_string();
+                advance(tokens, last);
+                k
             }
-            Some(t) => return Err(Error::UnexpectedToken(t.to_string())),
-            None => return Err(Error::UnexpectedEnd),
+            Some(t) => return Err(Error::UnexpectedToken(t.kind.to_string(), t.position)),
+            None => return Err(Error::UnexpectedEnd(*last)),
         };
 
         match tokens.first() {
-            Some(Token::NameSeparator) => *tokens = &tokens[1..],
-            Some(t) => return Err(Error::UnexpectedToken(t.to_string())),
-            None => return Err(Error::UnexpectedEnd),
+            Some(Token {
+                kind: TokenKind::NameSeparator,
+                ..
+            }) => advance(tokens, last),
+            Some(t) => return Err(Error::UnexpectedToken(t.kind.to_string(), t.position)),
+            None => return Err(Error::UnexpectedEnd(*last)),
         }
 
-        let v = parse_value(tokens)?;
+        let v = parse_value(tokens, last)?;
 
         o.insert(k, v);
     }
 }
 
-fn parse_array(tokens: &mut &[Token]) -> Result<Value, Error> {
+fn parse_array(tokens: &mut &[Token], last: &mut Position) -> Result<Value, Error> {
     match tokens.first() {
-        Some(Token::BeginArray) => *tokens = &tokens[1..],
-        Some(t) => return Err(Error::UnexpectedToken(t.to_string())),
-        None => return Err(Error::UnexpectedEnd),
+        Some(Token {
+            kind: TokenKind::BeginArray,
+            ..
+        }) => advance(tokens, last),
+        Some(t) => return Err(Error::UnexpectedToken(t.kind.to_string(), t.position)),
+        None => return Err(Error::UnexpectedEnd(*last)),
     }
 
     let mut a = Vec::new();
     loop {
-        if let Some(Token::EndArray) = tokens.first() {
-            *tokens = &tokens[1..];
+        if let Some(Token {
+            kind: TokenKind::EndArray,
+            ..
+        }) = tokens.first()
+        {
+            advance(tokens, last);
             return Ok(Value::Array(a));
         }
 
         if !a.is_empty() {
             match tokens.first() {
-                Some(Token::ValueSeparator) => *tokens = &tokens[1..],
-                Some(t) => return Err(Error::UnexpectedToken(t.to_string())),
-                None => return Err(Error::UnexpectedEnd),
+                Some(Token {
+                    kind: TokenKind::ValueSeparator,
+                    ..
+                }) => advance(tokens, last),
+                Some(t) => return Err(Error::UnexpectedToken(t.kind.to_string(), t.position)),
+                None => return Err(Error::UnexpectedEnd(*last)),
             }
         }
 
-        let v = parse_value(tokens)?;
+        let v = parse_value(tokens, last)?;
         a.push(v);
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::number::Number;
+
     use super::*;
 
+    fn tok(kind: TokenKind, line: usize, column: usize) -> Token {
+        Token {
+            kind,
+            position: Position { line, column },
+        }
+    }
+
+    fn parse_value_from(tokens: &[Token]) -> Result<Value, Error> {
+        let mut p = tokens;
+        let mut last = Position { line: 1, column: 1 };
+        parse_value(&mut p, &mut last)
+    }
+
     #[test]
     fn parse_value_test() {
         assert_eq!(
-            parse_value(&mut vec![Token::Null].as_slice()),
+            parse_value_from(&[tok(TokenKind::Null, 1, 1)]),
             Ok(Value::Null)
         );
         assert_eq!(
-            parse_value(&mut vec![Token::False].as_slice()),
+            parse_value_from(&[tok(TokenKind::False, 1, 1)]),
             Ok(Value::Bool(false))
         );
         assert_eq!(
-            parse_value(&mut vec![Token::True].as_slice()),
+            parse_value_from(&[tok(TokenKind::True, 1, 1)]),
             Ok(Value::Bool(true))
         );
         assert_eq!(
-            parse_value(&mut vec![Token::Number(123.45)].as_slice()),
-            Ok(Value::Number(123.45))
+            parse_value_from(&[tok(TokenKind::Number(Number::Float(123.45)), 1, 1)]),
+            Ok(Value::Number(Number::Float(123.45)))
         );
         assert_eq!(
-            parse_value(&mut vec![Token::String("value")].as_slice()),
+            parse_value_from(&[tok(TokenKind::String("value".to_string()), 1, 1)]),
             Ok(Value::String("value".to_string()))
         );
         assert_eq!(
-            parse_value(
-                &mut vec![
-                    Token::BeginObject,
-                    Token::String("keyA"),
-                    Token::NameSeparator,
-                    Token::String("valueA"),
-                    Token::EndObject,
-                ]
-                .as_slice()
-            ),
+            parse_value_from(&[
+                tok(TokenKind::BeginObject, 1, 1),
+                tok(TokenKind::String("keyA".to_string()), 1, 2),
+                tok(TokenKind::NameSeparator, 1, 8),
+                tok(TokenKind::String("valueA".to_string()), 1, 9),
+                tok(TokenKind::EndObject, 1, 17),
+            ]),
             Ok(Value::Object(
                 [("keyA".to_string(), Value::String("valueA".to_string()))].into()
             ))
         );
         assert_eq!(
-            parse_value(
-                &mut vec![
-                    Token::BeginArray,
-                    Token::String("value1"),
-                    Token::ValueSeparator,
-                    Token::String("value2"),
-                    Token::ValueSeparator,
-                    Token::String("value3"),
-                    Token::EndArray,
-                ]
-                .as_slice()
-            ),
+            parse_value_from(&[
+                tok(TokenKind::BeginArray, 1, 1),
+                tok(TokenKind::String("value1".to_string()), 1, 2),
+                tok(TokenKind::ValueSeparator, 1, 10),
+                tok(TokenKind::String("value2".to_string()), 1, 11),
+                tok(TokenKind::ValueSeparator, 1, 19),
+                tok(TokenKind::String("value3".to_string()), 1, 20),
+                tok(TokenKind::EndArray, 1, 28),
+            ]),
             Ok(Value::Array(vec![
                 Value::String("value1".to_string()),
                 Value::String("value2".to_string()),
                 Value::String("value3".to_string()),
             ]))
         );
+        assert_eq!(
+            parse_value_from(&[tok(TokenKind::ValueSeparator, 3, 4)]),
+            Err(Error::UnexpectedToken(
+                ",".to_string(),
+                Position { line: 3, column: 4 }
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_unexpected_end_reports_last_consumed_position() {
+        let tokens = [
+            tok(TokenKind::BeginObject, 1, 1),
+            tok(TokenKind::String("a".to_string()), 2, 3),
+            tok(TokenKind::NameSeparator, 2, 6),
+            tok(TokenKind::Number(Number::Int(1)), 2, 8),
+            tok(TokenKind::ValueSeparator, 2, 9),
+            tok(TokenKind::String("b".to_string()), 3, 3),
+            tok(TokenKind::NameSeparator, 3, 6),
+        ];
+
+        assert_eq!(
+            parse(&tokens),
+            Err(Error::UnexpectedEnd(Position { line: 3, column: 6 }))
+        );
     }
 }
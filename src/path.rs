@@ -0,0 +1,452 @@
+use crate::{error::Error, json::Value};
+
+#[derive(Debug, PartialEq)]
+pub enum Step {
+    Root,
+    Child(String),
+    RecursiveDescent,
+    Wildcard,
+    Index(isize),
+    Slice {
+        start: Option<isize>,
+        end: Option<isize>,
+        step: Option<isize>,
+    },
+    Union(Vec<isize>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Path {
+    steps: Vec<Step>,
+}
+
+impl Path {
+    pub fn compile(expr: &str) -> Result<Path, Error> {
+        let mut p = match expr.strip_prefix('$') {
+            Some(rest) => rest,
+            None => return Err(Error::InvalidPath(expr.to_string())),
+        };
+
+        let mut steps = vec![Step::Root];
+
+        while !p.is_empty() {
+            if let Some(rest) = p.strip_prefix("..") {
+                steps.push(Step::RecursiveDescent);
+                p = rest;
+
+                if p.starts_with('[') {
+                    continue;
+                }
+
+                let (cnt, key) = expect_key(p, expr)?;
+                p = &p[cnt..];
+                steps.push(if key == "*" {
+                    Step::Wildcard
+                } else {
+                    Step::Child(key)
+                });
+                continue;
+            }
+
+            if let Some(rest) = p.strip_prefix('.') {
+                let (cnt, key) = expect_key(rest, expr)?;
+                p = &rest[cnt..];
+                steps.push(if key == "*" {
+                    Step::Wildcard
+                } else {
+                    Step::Child(key)
+                });
+                continue;
+            }
+
+            if let Some(rest) = p.strip_prefix('[') {
+                let (cnt, step) = expect_bracket(rest, expr)?;
+                p = &rest[cnt..];
+                steps.push(step);
+                continue;
+            }
+
+            return Err(Error::InvalidPath(expr.to_string()));
+        }
+
+        Ok(Path { steps })
+    }
+
+    pub fn select<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![root];
+
+        for step in &self.steps {
+            current = match step {
+                Step::Root => current,
+                Step::Child(key) => current
+                    .into_iter()
+                    .filter_map(|v| match v {
+                        Value::Object(o) => o.get(key),
+                        _ => None,
+                    })
+                    .collect(),
+                Step::RecursiveDescent => current.into_iter().flat_map(collect_recursive).collect(),
+                Step::Wildcard => current.into_iter().flat_map(children).collect(),
+                Step::Index(i) => current
+                    .into_iter()
+                    .filter_map(|v| match v {
+                        Value::Array(a) => index(a, *i),
+                        _ => None,
+                    })
+                    .collect(),
+                Step::Slice { start, end, step } => current
+                    .into_iter()
+                    .flat_map(|v| match v {
+                        Value::Array(a) => slice(a, *start, *end, *step),
+                        _ => Vec::new(),
+                    })
+                    .collect(),
+                Step::Union(indices) => current
+                    .into_iter()
+                    .flat_map(|v| match v {
+                        Value::Array(a) => indices.iter().filter_map(|i| index(a, *i)).collect(),
+                        _ => Vec::new(),
+                    })
+                    .collect(),
+            };
+        }
+
+        current
+    }
+}
+
+fn children(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Object(o) => o.values().collect(),
+        Value::Array(a) => a.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn collect_recursive(value: &Value) -> Vec<&Value> {
+    let mut acc = vec![value];
+    for child in children(value) {
+        acc.extend(collect_recursive(child));
+    }
+    acc
+}
+
+fn index(a: &[Value], i: isize) -> Option<&Value> {
+    let len = a.len() as isize;
+    let i = if i < 0 { len + i } else { i };
+    if i < 0 || i >= len {
+        None
+    } else {
+        a.get(i as usize)
+    }
+}
+
+fn slice(
+    a: &[Value],
+    start: Option<isize>,
+    end: Option<isize>,
+    step: Option<isize>,
+) -> Vec<&Value> {
+    let len = a.len() as isize;
+    let step = step.unwrap_or(1).max(1);
+
+    let normalize = |i: isize| -> isize {
+        if i < 0 {
+            (len + i).clamp(0, len)
+        } else {
+            i.clamp(0, len)
+        }
+    };
+
+    let start = start.map(normalize).unwrap_or(0);
+    let end = end.map(normalize).unwrap_or(len);
+
+    let mut result = Vec::new();
+    let mut i = start;
+    while i < end {
+        if let Some(v) = a.get(i as usize) {
+            result.push(v);
+        }
+        i += step;
+    }
+    result
+}
+
+fn expect_key(input: &str, expr: &str) -> Result<(usize, String), Error> {
+    let end = input.find(['.', '[']).unwrap_or(input.len());
+    if end == 0 {
+        return Err(Error::InvalidPath(expr.to_string()));
+    }
+    Ok((end, input[..end].to_string()))
+}
+
+fn expect_bracket(input: &str, expr: &str) -> Result<(usize, Step), Error> {
+    let end = input
+        .find(']')
+        .ok_or_else(|| Error::InvalidPath(expr.to_string()))?;
+    let content = &input[..end];
+    let cnt = end + 1;
+
+    let quoted = content
+        .strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+        .or_else(|| content.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+
+    let step = if content == "*" {
+        Step::Wildcard
+    } else if let Some(key) = quoted {
+        Step::Child(key.to_string())
+    } else if content.contains(':') {
+        let parts: Vec<&str> = content.split(':').collect();
+        if parts.is_empty() || parts.len() > 3 {
+            return Err(Error::InvalidPath(expr.to_string()));
+        }
+
+        let parse_part = |s: &str| -> Result<Option<isize>, Error> {
+            if s.is_empty() {
+                Ok(None)
+            } else {
+                s.parse()
+                    .map(Some)
+                    .map_err(|_| Error::InvalidPath(expr.to_string()))
+            }
+        };
+
+        let step = parse_part(parts.get(2).copied().unwrap_or(""))?;
+        if step.is_some_and(|s| s < 0) {
+            // `slice` only walks forward and clamps the step to at least 1; a negative step
+            // would otherwise silently give a forward-order (often empty) result instead of
+            // the reverse-order result its syntax implies.
+            return Err(Error::InvalidPath(expr.to_string()));
+        }
+
+        Step::Slice {
+            start: parse_part(parts[0])?,
+            end: parse_part(parts.get(1).copied().unwrap_or(""))?,
+            step,
+        }
+    } else if content.contains(',') {
+        let indices = content
+            .split(',')
+            .map(|s| {
+                s.trim()
+                    .parse()
+                    .map_err(|_| Error::InvalidPath(expr.to_string()))
+            })
+            .collect::<Result<Vec<isize>, Error>>()?;
+        Step::Union(indices)
+    } else {
+        let i = content
+            .parse()
+            .map_err(|_| Error::InvalidPath(expr.to_string()))?;
+        Step::Index(i)
+    };
+
+    Ok((cnt, step))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number::Number;
+
+    use super::*;
+
+    #[test]
+    fn compile_test() {
+        assert_eq!(
+            Path::compile("$").unwrap(),
+            Path {
+                steps: vec![Step::Root]
+            }
+        );
+        assert_eq!(
+            Path::compile("$.store.book").unwrap(),
+            Path {
+                steps: vec![
+                    Step::Root,
+                    Step::Child("store".to_string()),
+                    Step::Child("book".to_string()),
+                ]
+            }
+        );
+        assert_eq!(
+            Path::compile("$['store']['book']").unwrap(),
+            Path {
+                steps: vec![
+                    Step::Root,
+                    Step::Child("store".to_string()),
+                    Step::Child("book".to_string()),
+                ]
+            }
+        );
+        assert_eq!(
+            Path::compile("$.store.book[*].author").unwrap(),
+            Path {
+                steps: vec![
+                    Step::Root,
+                    Step::Child("store".to_string()),
+                    Step::Child("book".to_string()),
+                    Step::Wildcard,
+                    Step::Child("author".to_string()),
+                ]
+            }
+        );
+        assert_eq!(
+            Path::compile("$..author").unwrap(),
+            Path {
+                steps: vec![
+                    Step::Root,
+                    Step::RecursiveDescent,
+                    Step::Child("author".to_string()),
+                ]
+            }
+        );
+        assert_eq!(
+            Path::compile("$.store.book[0]").unwrap(),
+            Path {
+                steps: vec![
+                    Step::Root,
+                    Step::Child("store".to_string()),
+                    Step::Child("book".to_string()),
+                    Step::Index(0),
+                ]
+            }
+        );
+        assert_eq!(
+            Path::compile("$.store.book[-1]").unwrap(),
+            Path {
+                steps: vec![
+                    Step::Root,
+                    Step::Child("store".to_string()),
+                    Step::Child("book".to_string()),
+                    Step::Index(-1),
+                ]
+            }
+        );
+        assert_eq!(
+            Path::compile("$.store.book[0:2]").unwrap(),
+            Path {
+                steps: vec![
+                    Step::Root,
+                    Step::Child("store".to_string()),
+                    Step::Child("book".to_string()),
+                    Step::Slice {
+                        start: Some(0),
+                        end: Some(2),
+                        step: None,
+                    },
+                ]
+            }
+        );
+        assert_eq!(
+            Path::compile("$.store.book[0,2]").unwrap(),
+            Path {
+                steps: vec![
+                    Step::Root,
+                    Step::Child("store".to_string()),
+                    Step::Child("book".to_string()),
+                    Step::Union(vec![0, 2]),
+                ]
+            }
+        );
+        assert_eq!(
+            Path::compile("store"),
+            Err(Error::InvalidPath("store".to_string()))
+        );
+    }
+
+    #[test]
+    fn compile_error_message_test() {
+        assert_eq!(
+            Path::compile("$.store.book[").unwrap_err().to_string(),
+            "invalid path '$.store.book['"
+        );
+        assert_eq!(
+            Path::compile("$.foo.").unwrap_err().to_string(),
+            "invalid path '$.foo.'"
+        );
+        assert_eq!(
+            Path::compile("$[8:2:-1]"),
+            Err(Error::InvalidPath("$[8:2:-1]".to_string()))
+        );
+    }
+
+    #[test]
+    fn select_test() {
+        let value: Value = r#"
+            {
+                "store": {
+                    "book": [
+                        {"author": "A", "price": 10},
+                        {"author": "B", "price": 20}
+                    ],
+                    "bicycle": {"price": 5}
+                }
+            }
+            "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            Path::compile("$.store.bicycle.price")
+                .unwrap()
+                .select(&value),
+            vec![&Value::Number(Number::Int(5))]
+        );
+
+        assert_eq!(
+            Path::compile("$.store.book[*].author")
+                .unwrap()
+                .select(&value),
+            vec![
+                &Value::String("A".to_string()),
+                &Value::String("B".to_string())
+            ]
+        );
+
+        assert_eq!(
+            Path::compile("$..author").unwrap().select(&value),
+            vec![
+                &Value::String("A".to_string()),
+                &Value::String("B".to_string())
+            ]
+        );
+
+        assert_eq!(
+            Path::compile("$.store.book[0]").unwrap().select(&value),
+            vec![value_at(&value, "store", "book", 0)]
+        );
+
+        assert_eq!(
+            Path::compile("$.store.book[-1].author")
+                .unwrap()
+                .select(&value),
+            vec![&Value::String("B".to_string())]
+        );
+
+        assert_eq!(
+            Path::compile("$.store.book[0:1].author")
+                .unwrap()
+                .select(&value),
+            vec![&Value::String("A".to_string())]
+        );
+    }
+
+    fn value_at<'a>(
+        value: &'a Value,
+        object_key: &str,
+        array_key: &str,
+        index: usize,
+    ) -> &'a Value {
+        match value {
+            Value::Object(o) => match o.get(object_key).unwrap() {
+                Value::Object(o) => match o.get(array_key).unwrap() {
+                    Value::Array(a) => &a[index],
+                    _ => panic!("expected array"),
+                },
+                _ => panic!("expected object"),
+            },
+            _ => panic!("expected object"),
+        }
+    }
+}
@@ -1,9 +1,21 @@
-use std::str;
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    str,
+};
 
-use crate::error::Error;
+use crate::{
+    error::{Error, Position},
+    number::{self, Number},
+};
 
 #[derive(Debug, PartialEq)]
-pub enum Token<'a> {
+pub struct Token {
+    pub kind: TokenKind,
+    pub position: Position,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TokenKind {
     BeginArray,
     BeginObject,
     EndArray,
@@ -13,72 +25,141 @@ pub enum Token<'a> {
     False,
     Null,
     True,
-    Number(f64),
-    String(&'a str),
+    Number(Number),
+    String(String),
+}
+
+impl Display for TokenKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::BeginArray => write!(f, "["),
+            Self::BeginObject => write!(f, "{{"),
+            Self::EndArray => write!(f, "]"),
+            Self::EndObject => write!(f, "}}"),
+            Self::NameSeparator => write!(f, ":"),
+            Self::ValueSeparator => write!(f, ","),
+            Self::False => write!(f, "false"),
+            Self::Null => write!(f, "null"),
+            Self::True => write!(f, "true"),
+            Self::Number(n) => write!(f, "{n}"),
+            Self::String(s) => write!(f, "\"{s}\""),
+        }
+    }
 }
 
 pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
     let mut tokens = Vec::new();
 
     let mut p = input;
+    let mut position = Position { line: 1, column: 1 };
 
     while let Some(&c) = p.as_bytes().first() {
         match c {
-            b' ' | b'\t' | b'\n' | b'\r' => {
+            b' ' | b'\t' | b'\r' => {
+                p = &p[1..];
+                position.column += 1;
+            }
+            b'\n' => {
                 p = &p[1..];
+                position.line += 1;
+                position.column = 1;
             }
             b'[' => {
-                tokens.push(Token::BeginArray);
+                tokens.push(Token {
+                    kind: TokenKind::BeginArray,
+                    position,
+                });
                 p = &p[1..];
+                position.column += 1;
             }
             b'{' => {
-                tokens.push(Token::BeginObject);
+                tokens.push(Token {
+                    kind: TokenKind::BeginObject,
+                    position,
+                });
                 p = &p[1..];
+                position.column += 1;
             }
             b']' => {
-                tokens.push(Token::EndArray);
+                tokens.push(Token {
+                    kind: TokenKind::EndArray,
+                    position,
+                });
                 p = &p[1..];
+                position.column += 1;
             }
             b'}' => {
-                tokens.push(Token::EndObject);
+                tokens.push(Token {
+                    kind: TokenKind::EndObject,
+                    position,
+                });
                 p = &p[1..];
+                position.column += 1;
             }
             b':' => {
-                tokens.push(Token::NameSeparator);
+                tokens.push(Token {
+                    kind: TokenKind::NameSeparator,
+                    position,
+                });
                 p = &p[1..];
+                position.column += 1;
             }
             b',' => {
-                tokens.push(Token::ValueSeparator);
+                tokens.push(Token {
+                    kind: TokenKind::ValueSeparator,
+                    position,
+                });
                 p = &p[1..];
+                position.column += 1;
             }
             b'"' => {
-                let (cnt, s) = expect_string(p)?;
-                tokens.push(Token::String(s));
+                let (cnt, s) = expect_string(p, position)?;
+                tokens.push(Token {
+                    kind: TokenKind::String(s),
+                    position,
+                });
+                position = advance(position, &p[..cnt]);
                 p = &p[cnt..];
             }
             b'-' | b'0'..=b'9' => {
-                let (cnt, n) = expect_number(p)?;
-                tokens.push(Token::Number(n));
+                let (cnt, n) = expect_number(p, position)?;
+                tokens.push(Token {
+                    kind: TokenKind::Number(n),
+                    position,
+                });
+                position = advance(position, &p[..cnt]);
                 p = &p[cnt..];
             }
             b'f' => {
-                expect_false(p)?;
-                tokens.push(Token::False);
+                expect_false(p, position)?;
+                tokens.push(Token {
+                    kind: TokenKind::False,
+                    position,
+                });
+                position = advance(position, &p[..5]);
                 p = &p[5..];
             }
             b'n' => {
-                expect_null(p)?;
-                tokens.push(Token::Null);
+                expect_null(p, position)?;
+                tokens.push(Token {
+                    kind: TokenKind::Null,
+                    position,
+                });
+                position = advance(position, &p[..4]);
                 p = &p[4..];
             }
             b't' => {
-                expect_true(p)?;
-                tokens.push(Token::True);
+                expect_true(p, position)?;
+                tokens.push(Token {
+                    kind: TokenKind::True,
+                    position,
+                });
+                position = advance(position, &p[..4]);
                 p = &p[4..];
             }
             _ => {
                 let c = p.chars().next().unwrap();
-                return Err(Error::UnexpectedToken(c));
+                return Err(Error::UnexpectedChar(c, position));
             }
         }
     }
@@ -86,9 +167,22 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
     Ok(tokens)
 }
 
-fn expect_number(input: &str) -> Result<(usize, f64), Error> {
+fn advance(mut position: Position, s: &str) -> Position {
+    for c in s.chars() {
+        if c == '\n' {
+            position.line += 1;
+            position.column = 1;
+        } else {
+            position.column += 1;
+        }
+    }
+    position
+}
+
+fn expect_number(input: &str, position: Position) -> Result<(usize, Number), Error> {
     let mut iter = input.chars().peekable();
     let mut cnt = 0;
+    let mut is_float = false;
 
     // minus (optional)
     if let Some('-') = iter.peek() {
@@ -106,6 +200,7 @@ fn expect_number(input: &str) -> Result<(usize, f64), Error> {
     if let Some('.') = iter.peek() {
         iter.next();
         cnt += 1;
+        is_float = true;
 
         while let Some('0'..='9') = iter.peek() {
             iter.next();
@@ -117,6 +212,7 @@ fn expect_number(input: &str) -> Result<(usize, f64), Error> {
     if let Some('e') = iter.peek() {
         iter.next();
         cnt += 1;
+        is_float = true;
 
         if let Some('+' | '-') = iter.peek() {
             iter.next();
@@ -130,52 +226,130 @@ fn expect_number(input: &str) -> Result<(usize, f64), Error> {
     }
 
     let s = &input[..cnt];
-    let n = s.parse().unwrap();
+    let n = if is_float {
+        let f: f64 = s
+            .parse()
+            .map_err(|_| Error::UnexpectedChar(s.chars().next().unwrap_or('\0'), position))?;
+        if f.is_finite() {
+            Number::Float(f)
+        } else {
+            // Exponent-heavy literals like `1e400` overflow `f64::parse` to +/-inf; fall back
+            // to the original digit string so no precision is lost and the value still
+            // round-trips back to valid JSON text.
+            Number::Big(s.to_string())
+        }
+    } else {
+        number::parse_integer(s)
+            .ok_or_else(|| Error::UnexpectedChar(s.chars().next().unwrap_or('\0'), position))?
+    };
 
     Ok((cnt, n))
 }
 
-fn expect_string(input: &str) -> Result<(usize, &str), Error> {
-    let mut iter = input.chars();
+fn expect_string(input: &str, position: Position) -> Result<(usize, String), Error> {
+    let mut p = input;
     let mut cnt = 0;
-    match iter.next() {
-        Some(t) if t != '"' => {
-            return Err(Error::UnexpectedToken(t));
+
+    match p.as_bytes().first() {
+        Some(b'"') => {
+            p = &p[1..];
+            cnt += 1;
         }
-        None => {
-            return Err(Error::UnexpectedEnd);
+        Some(_) => {
+            return Err(Error::UnexpectedChar(p.chars().next().unwrap(), position));
         }
-        _ => {
-            cnt += 1;
+        None => {
+            return Err(Error::UnexpectedEnd(position));
         }
     }
 
+    let mut s = String::new();
+
     loop {
-        match iter.next() {
-            Some('"') => {
-                let s = &input[1..cnt];
+        match p.as_bytes().first() {
+            Some(b'"') => {
                 cnt += 1;
                 return Ok((cnt, s));
             }
-            Some(c) => {
+            Some(b'\\') => {
+                let (escape_cnt, c) = expect_escape(p, advance(position, &input[..cnt]))?;
+                s.push(c);
+                cnt += escape_cnt;
+                p = &p[escape_cnt..];
+            }
+            Some(_) => {
+                let c = p.chars().next().unwrap();
+                s.push(c);
                 cnt += c.len_utf8();
+                p = &p[c.len_utf8()..];
             }
             None => {
-                return Err(Error::UnexpectedEnd);
+                return Err(Error::UnexpectedEnd(advance(position, &input[..cnt])));
             }
         }
     }
 }
 
-fn expect_null(s: &str) -> Result<(), Error> {
+/// Decodes a single escape sequence starting at `\`, returning the bytes consumed and the
+/// scalar value it represents. `\uXXXX` surrogate pairs are combined into one `char`.
+fn expect_escape(input: &str, position: Position) -> Result<(usize, char), Error> {
+    let rest = &input[1..];
+    match rest.as_bytes().first() {
+        Some(b'"') => Ok((2, '"')),
+        Some(b'\\') => Ok((2, '\\')),
+        Some(b'/') => Ok((2, '/')),
+        Some(b'b') => Ok((2, '\u{0008}')),
+        Some(b'f') => Ok((2, '\u{000C}')),
+        Some(b'n') => Ok((2, '\n')),
+        Some(b'r') => Ok((2, '\r')),
+        Some(b't') => Ok((2, '\t')),
+        Some(b'u') => expect_unicode_escape(input, position),
+        Some(_) => Err(Error::InvalidEscape(position)),
+        None => Err(Error::UnexpectedEnd(position)),
+    }
+}
+
+fn expect_unicode_escape(input: &str, position: Position) -> Result<(usize, char), Error> {
+    let high = expect_hex4(input, position)?;
+
+    if (0xDC00..=0xDFFF).contains(&high) {
+        return Err(Error::InvalidEscape(position));
+    }
+
+    if !(0xD800..=0xDBFF).contains(&high) {
+        let c = char::from_u32(high as u32).ok_or(Error::InvalidEscape(position))?;
+        return Ok((6, c));
+    }
+
+    if input.as_bytes().get(6) != Some(&b'\\') || input.as_bytes().get(7) != Some(&b'u') {
+        return Err(Error::InvalidEscape(position));
+    }
+
+    let low = expect_hex4(&input[6..], advance(position, &input[..6]))?;
+    if !(0xDC00..=0xDFFF).contains(&low) {
+        return Err(Error::InvalidEscape(position));
+    }
+
+    let scalar = 0x10000 + (high as u32 - 0xD800) * 0x400 + (low as u32 - 0xDC00);
+    let c = char::from_u32(scalar).ok_or(Error::InvalidEscape(position))?;
+    Ok((12, c))
+}
+
+/// Parses the four hex digits of a `\uXXXX` escape, where `input` starts at the `\`.
+fn expect_hex4(input: &str, position: Position) -> Result<u16, Error> {
+    let hex = input.get(2..6).ok_or(Error::InvalidEscape(position))?;
+    u16::from_str_radix(hex, 16).map_err(|_| Error::InvalidEscape(position))
+}
+
+fn expect_null(s: &str, position: Position) -> Result<(), Error> {
     let mut iter = s.chars();
-    for c in ['n', 'u', 'l', 'l'] {
+    for (i, c) in ['n', 'u', 'l', 'l'].into_iter().enumerate() {
         match iter.next() {
             Some(t) if t != c => {
-                return Err(Error::UnexpectedToken(t));
+                return Err(Error::UnexpectedChar(t, advance(position, &s[..i])));
             }
             None => {
-                return Err(Error::UnexpectedEnd);
+                return Err(Error::UnexpectedEnd(advance(position, &s[..i])));
             }
             _ => {}
         }
@@ -183,15 +357,15 @@ fn expect_null(s: &str) -> Result<(), Error> {
     Ok(())
 }
 
-fn expect_false(s: &str) -> Result<(), Error> {
+fn expect_false(s: &str, position: Position) -> Result<(), Error> {
     let mut iter = s.chars();
-    for c in ['f', 'a', 'l', 's', 'e'] {
+    for (i, c) in ['f', 'a', 'l', 's', 'e'].into_iter().enumerate() {
         match iter.next() {
             Some(t) if t != c => {
-                return Err(Error::UnexpectedToken(t));
+                return Err(Error::UnexpectedChar(t, advance(position, &s[..i])));
             }
             None => {
-                return Err(Error::UnexpectedEnd);
+                return Err(Error::UnexpectedEnd(advance(position, &s[..i])));
             }
             _ => {}
         }
@@ -199,15 +373,15 @@ fn expect_false(s: &str) -> Result<(), Error> {
     Ok(())
 }
 
-fn expect_true(s: &str) -> Result<(), Error> {
+fn expect_true(s: &str, position: Position) -> Result<(), Error> {
     let mut iter = s.chars();
-    for c in ['t', 'r', 'u', 'e'] {
+    for (i, c) in ['t', 'r', 'u', 'e'].into_iter().enumerate() {
         match iter.next() {
             Some(t) if t != c => {
-                return Err(Error::UnexpectedToken(t));
+                return Err(Error::UnexpectedChar(t, advance(position, &s[..i])));
             }
             None => {
-                return Err(Error::UnexpectedEnd);
+                return Err(Error::UnexpectedEnd(advance(position, &s[..i])));
             }
             _ => {}
         }
@@ -219,156 +393,162 @@ fn expect_true(s: &str) -> Result<(), Error> {
 mod tests {
     use super::*;
 
+    fn tok(kind: TokenKind, line: usize, column: usize) -> Token {
+        Token {
+            kind,
+            position: Position { line, column },
+        }
+    }
+
     #[test]
     fn it_works() {
-        assert_eq!(tokenize("["), Ok(vec![Token::BeginArray]));
-        assert_eq!(tokenize("{"), Ok(vec![Token::BeginObject]));
-        assert_eq!(tokenize("]"), Ok(vec![Token::EndArray]));
-        assert_eq!(tokenize("}"), Ok(vec![Token::EndObject]));
-        assert_eq!(tokenize(":"), Ok(vec![Token::NameSeparator]));
-        assert_eq!(tokenize(","), Ok(vec![Token::ValueSeparator]));
-
-        assert_eq!(tokenize("false"), Ok(vec![Token::False]));
-        assert_eq!(tokenize("null"), Ok(vec![Token::Null]));
-        assert_eq!(tokenize("true"), Ok(vec![Token::True]));
-        assert_eq!(tokenize("12345"), Ok(vec![Token::Number(12345f64)]));
-        assert_eq!(tokenize("12345e123"), Ok(vec![Token::Number(12345e123f64)]));
+        assert_eq!(tokenize("["), Ok(vec![tok(TokenKind::BeginArray, 1, 1)]));
+        assert_eq!(tokenize("{"), Ok(vec![tok(TokenKind::BeginObject, 1, 1)]));
+        assert_eq!(tokenize("]"), Ok(vec![tok(TokenKind::EndArray, 1, 1)]));
+        assert_eq!(tokenize("}"), Ok(vec![tok(TokenKind::EndObject, 1, 1)]));
+        assert_eq!(tokenize(":"), Ok(vec![tok(TokenKind::NameSeparator, 1, 1)]));
+        assert_eq!(
+            tokenize(","),
+            Ok(vec![tok(TokenKind::ValueSeparator, 1, 1)])
+        );
+
+        assert_eq!(tokenize("false"), Ok(vec![tok(TokenKind::False, 1, 1)]));
+        assert_eq!(tokenize("null"), Ok(vec![tok(TokenKind::Null, 1, 1)]));
+        assert_eq!(tokenize("true"), Ok(vec![tok(TokenKind::True, 1, 1)]));
+        assert_eq!(
+            tokenize("12345"),
+            Ok(vec![tok(TokenKind::Number(Number::Int(12345)), 1, 1)])
+        );
         assert_eq!(
-            tokenize("12345e-123"),
-            Ok(vec![Token::Number(12345e-123f64)])
+            tokenize("\"string\""),
+            Ok(vec![tok(TokenKind::String("string".to_string()), 1, 1)])
         );
-        assert_eq!(tokenize("123.45"), Ok(vec![Token::Number(123.45f64)]));
+
         assert_eq!(
-            tokenize("123.45e123"),
-            Ok(vec![Token::Number(123.45e123f64)])
+            tokenize("[]"),
+            Ok(vec![
+                tok(TokenKind::BeginArray, 1, 1),
+                tok(TokenKind::EndArray, 1, 2),
+            ])
         );
         assert_eq!(
-            tokenize("123.45e-123"),
-            Ok(vec![Token::Number(123.45e-123f64)])
+            tokenize("{\n  \"key\": \"value\"\n}"),
+            Ok(vec![
+                tok(TokenKind::BeginObject, 1, 1),
+                tok(TokenKind::String("key".to_string()), 2, 3),
+                tok(TokenKind::NameSeparator, 2, 8),
+                tok(TokenKind::String("value".to_string()), 2, 10),
+                tok(TokenKind::EndObject, 3, 1),
+            ])
         );
-        assert_eq!(tokenize("-12345"), Ok(vec![Token::Number(-12345f64)]));
+
         assert_eq!(
-            tokenize("-12345e123"),
-            Ok(vec![Token::Number(-12345e123f64)])
+            tokenize("{\n  ?\n}"),
+            Err(Error::UnexpectedChar('?', Position { line: 2, column: 3 }))
+        );
+    }
+
+    #[test]
+    fn number_test() {
+        assert_eq!(
+            tokenize("12345"),
+            Ok(vec![tok(TokenKind::Number(Number::Int(12345)), 1, 1)])
+        );
+        assert_eq!(
+            tokenize("-12345"),
+            Ok(vec![tok(TokenKind::Number(Number::Int(-12345)), 1, 1)])
+        );
+        assert_eq!(
+            tokenize("18446744073709551615"),
+            Ok(vec![tok(TokenKind::Number(Number::UInt(u64::MAX)), 1, 1)])
+        );
+        assert_eq!(
+            tokenize("99999999999999999999999"),
+            Ok(vec![tok(
+                TokenKind::Number(Number::Big("99999999999999999999999".to_string())),
+                1,
+                1
+            )])
+        );
+        assert_eq!(
+            tokenize("123.45"),
+            Ok(vec![tok(TokenKind::Number(Number::Float(123.45)), 1, 1)])
+        );
+        assert_eq!(
+            tokenize("12345e123"),
+            Ok(vec![tok(TokenKind::Number(Number::Float(12345e123)), 1, 1)])
         );
         assert_eq!(
             tokenize("-12345e-123"),
-            Ok(vec![Token::Number(-12345e-123f64)])
+            Ok(vec![tok(
+                TokenKind::Number(Number::Float(-12345e-123)),
+                1,
+                1
+            )])
         );
-        assert_eq!(tokenize("-123.45"), Ok(vec![Token::Number(-123.45f64)]));
         assert_eq!(
-            tokenize("-123.45e123"),
-            Ok(vec![Token::Number(-123.45e123f64)])
+            tokenize("1e400"),
+            Ok(vec![tok(
+                TokenKind::Number(Number::Big("1e400".to_string())),
+                1,
+                1
+            )])
         );
         assert_eq!(
-            tokenize("-123.45e-123"),
-            Ok(vec![Token::Number(-123.45e-123f64)])
+            tokenize("-"),
+            Err(Error::UnexpectedChar('-', Position { line: 1, column: 1 }))
         );
-        assert_eq!(tokenize("\"string\""), Ok(vec![Token::String("string")]));
+    }
 
-        assert_eq!(tokenize("[]"), Ok(vec![Token::BeginArray, Token::EndArray]));
+    #[test]
+    fn escape_test() {
         assert_eq!(
-            tokenize(
-                r#"
-                [
-                    false,
-                    null,
-                    true,
-                    12345,
-                    "string",
-                    [],
-                    {}
-                ]
-                "#
-            ),
-            Ok(vec![
-                Token::BeginArray,
-                Token::False,
-                Token::ValueSeparator,
-                Token::Null,
-                Token::ValueSeparator,
-                Token::True,
-                Token::ValueSeparator,
-                Token::Number(12345f64),
-                Token::ValueSeparator,
-                Token::String("string"),
-                Token::ValueSeparator,
-                Token::BeginArray,
-                Token::EndArray,
-                Token::ValueSeparator,
-                Token::BeginObject,
-                Token::EndObject,
-                Token::EndArray,
-            ])
+            tokenize(r#""line\nbreak""#),
+            Ok(vec![tok(
+                TokenKind::String("line\nbreak".to_string()),
+                1,
+                1
+            )])
         );
-
         assert_eq!(
-            tokenize("{}"),
-            Ok(vec![Token::BeginObject, Token::EndObject])
+            tokenize(r#""a\"b\\c\/d""#),
+            Ok(vec![tok(TokenKind::String("a\"b\\c/d".to_string()), 1, 1)])
         );
         assert_eq!(
-            tokenize(
-                r#"
-                {
-                    "key": "value"
-                }
-                "#
-            ),
-            Ok(vec![
-                Token::BeginObject,
-                Token::String("key"),
-                Token::NameSeparator,
-                Token::String("value"),
-                Token::EndObject
-            ])
+            tokenize(r#""tab\tret\rbs\bff\f""#),
+            Ok(vec![tok(
+                TokenKind::String("tab\tret\rbs\u{0008}ff\u{000C}".to_string()),
+                1,
+                1
+            )])
         );
         assert_eq!(
-            tokenize(
-                r#"
-                {
-                    "key0": false,
-                    "key1": null,
-                    "key2": true,
-                    "key3": 12345,
-                    "key4": "string",
-                    "key5": [],
-                    "key6": {}
-                }
-                "#
-            ),
-            Ok(vec![
-                Token::BeginObject,
-                Token::String("key0"),
-                Token::NameSeparator,
-                Token::False,
-                Token::ValueSeparator,
-                Token::String("key1"),
-                Token::NameSeparator,
-                Token::Null,
-                Token::ValueSeparator,
-                Token::String("key2"),
-                Token::NameSeparator,
-                Token::True,
-                Token::ValueSeparator,
-                Token::String("key3"),
-                Token::NameSeparator,
-                Token::Number(12345f64),
-                Token::ValueSeparator,
-                Token::String("key4"),
-                Token::NameSeparator,
-                Token::String("string"),
-                Token::ValueSeparator,
-                Token::String("key5"),
-                Token::NameSeparator,
-                Token::BeginArray,
-                Token::EndArray,
-                Token::ValueSeparator,
-                Token::String("key6"),
-                Token::NameSeparator,
-                Token::BeginObject,
-                Token::EndObject,
-                Token::EndObject
-            ])
+            tokenize(r#""é""#),
+            Ok(vec![tok(TokenKind::String("é".to_string()), 1, 1)])
+        );
+        assert_eq!(
+            tokenize(r#""😀""#),
+            Ok(vec![tok(TokenKind::String("😀".to_string()), 1, 1)])
+        );
+        assert_eq!(
+            tokenize(r#""\u00e9""#),
+            Ok(vec![tok(TokenKind::String("é".to_string()), 1, 1)])
+        );
+        assert_eq!(
+            tokenize(r#""\ud83d\ude00""#),
+            Ok(vec![tok(TokenKind::String("😀".to_string()), 1, 1)])
+        );
+        assert_eq!(
+            tokenize(r#""\ud83d""#),
+            Err(Error::InvalidEscape(Position { line: 1, column: 2 }))
+        );
+        assert_eq!(
+            tokenize(r#""\udc00""#),
+            Err(Error::InvalidEscape(Position { line: 1, column: 2 }))
+        );
+        assert_eq!(
+            tokenize(r#""\q""#),
+            Err(Error::InvalidEscape(Position { line: 1, column: 2 }))
         );
     }
 }